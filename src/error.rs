@@ -0,0 +1,31 @@
+/// Errors that can occur while operating the [`Radar`][`crate::Radar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadarError {
+    /// The radar was asked to scan again before its cooldown had elapsed.
+    ///
+    /// `time_to_ready` is the estimated number of ticks left before the radar
+    /// can be used again, so callers can decide whether to spin-wait with
+    /// [`Radar::wait`][`crate::Radar::wait`] or go do something else first.
+    NotReady { time_to_ready: u32 },
+    /// `scan` was called with a size other than 3, 5, 7, or 9.
+    InvalidSize { got: usize },
+}
+
+impl core::fmt::Display for RadarError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RadarError::NotReady { time_to_ready } => {
+                write!(f, "radar isn't ready for {time_to_ready} more ticks")
+            }
+            RadarError::InvalidSize { got } => {
+                write!(f, "invalid scan size {got}, must be one of 3, 5, 7, 9")
+            }
+        }
+    }
+}
+
+impl core::error::Error for RadarError {}
+
+/// Crate-level `Result` alias so callers don't have to spell out
+/// [`RadarError`] on every signature.
+pub type Result<T> = core::result::Result<T, RadarError>;
@@ -0,0 +1,192 @@
+use kartoffel::radar_read;
+
+/// Largest grid a single scan can produce (a 9x9 scan).
+const MAX_GRID: usize = 9 * 9;
+
+/// How many past scans we keep around. Kept small since this runs on a
+/// memory-constrained bot.
+const HISTORY_CAPACITY: usize = 6;
+
+/// A single scan, captured in full immediately after `radar_scan` fires so
+/// that a later, smaller scan doesn't clobber the tiles we cared about.
+#[derive(Clone, Copy)]
+struct ScanSnapshot {
+    /// the size of this scan, e.g. 3, 5, 7, 9
+    size: usize,
+    /// the tick this scan went off
+    tick: u32,
+    /// the raw `radar_read` grid, row-major, centered on the bot
+    grid: [u8; MAX_GRID],
+}
+
+impl ScanSnapshot {
+    const fn empty() -> Self {
+        ScanSnapshot {
+            size: 0,
+            tick: 0,
+            grid: [0; MAX_GRID],
+        }
+    }
+
+    /// Does this snapshot cover tile `(x, y)`?
+    fn covers(&self, x: i8, y: i8) -> bool {
+        let half = (self.size / 2) as i8;
+        x.abs() <= half && y.abs() <= half
+    }
+
+    /// The character observed at `(x, y)`, assuming [`covers`][Self::covers] is true.
+    fn at(&self, x: i8, y: i8) -> char {
+        let half = (self.size / 2) as i32;
+        let row = (y as i32 + half) as usize;
+        let col = (x as i32 + half) as usize;
+        self.grid[row * self.size + col] as char
+    }
+}
+
+/// Fixed-capacity ring buffer of recent scan snapshots.
+///
+/// `head` points at the slot the *next* push will overwrite and `size`
+/// tracks how many of the `entries` are actually populated, so early in a
+/// bot's life (before the buffer has wrapped round) we don't hand out
+/// garbage entries.
+pub(crate) struct ScanHistory {
+    entries: [ScanSnapshot; HISTORY_CAPACITY],
+    head: usize,
+    size: usize,
+}
+
+impl ScanHistory {
+    pub(crate) const fn new() -> Self {
+        ScanHistory {
+            entries: [ScanSnapshot::empty(); HISTORY_CAPACITY],
+            head: 0,
+            size: 0,
+        }
+    }
+
+    /// Captures the full grid of a scan that just fired and pushes it into
+    /// the ring buffer, overwriting the oldest entry once full.
+    pub(crate) fn push(&mut self, size: usize, tick: u32) {
+        let half = (size / 2) as i8;
+        let mut grid = [0u8; MAX_GRID];
+        for dy in -half..=half {
+            for dx in -half..=half {
+                let row = (dy + half) as usize;
+                let col = (dx + half) as usize;
+                grid[row * size + col] = radar_read(size, dx, dy, 0) as u8;
+            }
+        }
+        self.insert(ScanSnapshot { size, tick, grid });
+    }
+
+    /// Ring-buffer bookkeeping shared by [`push`][Self::push] and (in tests)
+    /// synthetic snapshots that don't need a real `radar_read`.
+    fn insert(&mut self, snapshot: ScanSnapshot) {
+        self.entries[self.head] = snapshot;
+        self.head = (self.head + 1) % HISTORY_CAPACITY;
+        if self.size < HISTORY_CAPACITY {
+            self.size += 1;
+        }
+    }
+
+    /// Every past observation of tile `(x, y)`, newest first.
+    pub(crate) fn history_at(&self, x: i8, y: i8) -> impl Iterator<Item = (char, u32)> + '_ {
+        (0..self.size)
+            .map(move |i| &self.entries[(self.head + HISTORY_CAPACITY - 1 - i) % HISTORY_CAPACITY])
+            .filter(move |snapshot| snapshot.covers(x, y))
+            .map(move |snapshot| (snapshot.at(x, y), snapshot.tick))
+    }
+
+    /// Compares the two most recent observations of tile `(x, y)` taken at
+    /// or after `tick`, flagging whether its contents changed between them.
+    ///
+    /// Returns `None` if there aren't two such observations to compare yet.
+    pub(crate) fn changed_since(&self, x: i8, y: i8, tick: u32) -> Option<bool> {
+        let mut observations = self.history_at(x, y).filter(|&(_, t)| t >= tick);
+        let newest = observations.next()?;
+        let previous = observations.next()?;
+        Some(newest.0 != previous.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 3x3 snapshot where every tile reads `fill`, taken at `tick`.
+    fn snapshot_3x3(fill: u8, tick: u32) -> ScanSnapshot {
+        ScanSnapshot {
+            size: 3,
+            tick,
+            grid: {
+                let mut grid = [0u8; MAX_GRID];
+                grid[..9].fill(fill);
+                grid
+            },
+        }
+    }
+
+    #[test]
+    fn history_at_returns_observations_newest_first() {
+        let mut history = ScanHistory::new();
+        history.insert(snapshot_3x3(b'.', 100));
+        history.insert(snapshot_3x3(b'#', 200));
+
+        assert!(history
+            .history_at(0, 0)
+            .eq([('#', 200), ('.', 100)].into_iter()));
+    }
+
+    #[test]
+    fn history_at_skips_snapshots_that_dont_cover_the_tile() {
+        let mut history = ScanHistory::new();
+        // A 3x3 scan can't see tile (2, 0).
+        history.insert(snapshot_3x3(b'.', 100));
+        assert_eq!(history.history_at(2, 0).next(), None);
+    }
+
+    #[test]
+    fn ring_buffer_overwrites_oldest_entry_once_full() {
+        let mut history = ScanHistory::new();
+        for tick in 0..HISTORY_CAPACITY as u32 {
+            history.insert(snapshot_3x3(b'.', tick));
+        }
+        // One more push should evict the oldest (tick 0), not tick 1.
+        history.insert(snapshot_3x3(b'.', HISTORY_CAPACITY as u32));
+
+        let count = history.history_at(0, 0).count();
+        assert_eq!(count, HISTORY_CAPACITY);
+        assert!(history.history_at(0, 0).all(|(_, t)| t != 0));
+        assert!(history.history_at(0, 0).any(|(_, t)| t == 1));
+        assert!(history
+            .history_at(0, 0)
+            .any(|(_, t)| t == HISTORY_CAPACITY as u32));
+    }
+
+    #[test]
+    fn changed_since_detects_a_change_between_the_two_newest_observations() {
+        let mut history = ScanHistory::new();
+        history.insert(snapshot_3x3(b'.', 100));
+        history.insert(snapshot_3x3(b'@', 200));
+        assert_eq!(history.changed_since(0, 0, 0), Some(true));
+    }
+
+    #[test]
+    fn changed_since_reports_no_change_when_readings_match() {
+        let mut history = ScanHistory::new();
+        history.insert(snapshot_3x3(b'.', 100));
+        history.insert(snapshot_3x3(b'.', 200));
+        assert_eq!(history.changed_since(0, 0, 0), Some(false));
+    }
+
+    #[test]
+    fn changed_since_is_none_without_two_qualifying_observations() {
+        let mut history = ScanHistory::new();
+        history.insert(snapshot_3x3(b'.', 100));
+        assert_eq!(history.changed_since(0, 0, 0), None);
+
+        // Only one of the two observations is at/after the given tick.
+        history.insert(snapshot_3x3(b'@', 200));
+        assert_eq!(history.changed_since(0, 0, 150), None);
+    }
+}
@@ -1,6 +1,60 @@
-use core::fmt::Error;
 use kartoffel::*;
 
+mod error;
+mod history;
+mod scheduler;
+mod world_map;
+pub use error::{RadarError, Result};
+pub use scheduler::{ScanScheduler, ScanTarget};
+pub use world_map::{Consolidation, Heading, WorldMap};
+
+use history::ScanHistory;
+
+/// The radar cooldown, in ticks, for a given scan size.
+fn cooldown_ticks(size: usize) -> u32 {
+    match size {
+        3 => 10_000,
+        5 => 15_000,
+        7 => 22_000,
+        9 => 30_000,
+        _ => 0,
+    }
+}
+
+/// The documented jitter on a scan size's cooldown, as a fraction (e.g. 0.10 for ±10%).
+fn jitter_fraction(size: usize) -> f32 {
+    match size {
+        3 => 0.10,
+        5 => 0.15,
+        7 => 0.25,
+        9 => 0.30,
+        _ => 0.0,
+    }
+}
+
+/// Ticks remaining until `now` reaches `scanned_at + cooldown`, saturating to
+/// `0` once the cooldown has already elapsed instead of wrapping (mirroring
+/// how `std::time::Instant::duration_since` saturates rather than panics).
+fn ticks_until_ready(now: u32, scanned_at: u32, cooldown: u32) -> u32 {
+    (scanned_at + cooldown).saturating_sub(now)
+}
+
+/// The index into [`Radar::scan_time`] of the smallest scan size that covers
+/// tile `(x, y)`, or `None` if the tile is outside the radar's reach
+/// entirely (further than a 9x9 scan covers).
+fn covering_index(x: i8, y: i8) -> Option<usize> {
+    let a_x = x.unsigned_abs();
+    let a_y = y.unsigned_abs();
+    let mut bigger = (if a_x > a_y { a_x } else { a_y }) as usize;
+    if bigger == 0 {
+        bigger = 1
+    }
+    if bigger > 4 {
+        return None;
+    }
+    Some(bigger - 1)
+}
+
 /// Wrapper around the kartoffel radar functionality
 ///
 /// This is desgined to try and make reading the radar a bit more consistent.
@@ -15,6 +69,8 @@ pub struct Radar {
     pub recent_scan_type: usize,
     /// is an array of which scan type and when it took place for quick searching for each of the relevent sizes e.g. 0->3, 1->5, 2->7, 3->9
     pub scan_time: [(usize, u32); 4],
+    /// round-robin history of the last few full scans, newest overwriting oldest
+    history: ScanHistory,
 }
 
 impl Radar {
@@ -23,6 +79,7 @@ impl Radar {
             scan_time: [(3, 0), (5, 0), (7, 0), (9, 0)],
             recent_scan_type: 3,
             recent_scan_time: 0,
+            history: ScanHistory::new(),
         }
     }
 
@@ -47,7 +104,7 @@ impl Radar {
     ///
     /// let time = timer_ticks();
     /// let (scan_location,scan_time) = radar.at(-1,1);
-    /// if time - scan_time < 20_000 {
+    /// if time.saturating_sub(scan_time) < 20_000 {
     ///  // this data is quite fresh
     /// }
     /// ```
@@ -56,18 +113,61 @@ impl Radar {
     /// 3: 10_000, 5: 15_000, 7: 22_000, 9: 30_000,
     /// with each one having a +- of 10, 15, 25, 30 % respectively
     fn at(&self, x: i8, y: i8) -> Option<(char, u32)> {
-        // which scans can we use
-        let a_x = x.unsigned_abs();
-        let a_y = y.unsigned_abs();
-        let mut bigger = (if a_x > a_y { a_x } else { a_y }) as usize;
-        if bigger == 0 {
-            bigger = 1
+        let (scan_size, scanned_at) = self.scan_time[covering_index(x, y)?];
+        Some((radar_read(scan_size, x, y, 0) as u8 as char, scanned_at))
+    }
+
+    /// Every past observation of tile `(x, y)`, newest first.
+    ///
+    /// Unlike [`at`][`Radar::at`], which only remembers the single most
+    /// recent scan per size, this walks the round-robin history of full
+    /// scans so you can see how a tile has changed over the last few turns.
+    fn history_at(&self, x: i8, y: i8) -> impl Iterator<Item = (char, u32)> + '_ {
+        self.history.history_at(x, y)
+    }
+
+    /// Did tile `(x, y)` change between its two most recent observations
+    /// taken at or after `tick`?
+    ///
+    /// Handy for spotting moving enemies: scan the same tile twice and ask
+    /// whether what's there changed. Returns `None` if there aren't two such
+    /// observations yet.
+    fn changed_since(&self, x: i8, y: i8, tick: u32) -> Option<bool> {
+        self.history.changed_since(x, y, tick)
+    }
+
+    /// How much should you trust the value [`at`][`Radar::at`] currently returns for `(x, y)`?
+    ///
+    /// Returns a value in `[0.0, 1.0]`: ~1.0 while the covering scan is well
+    /// within its cooldown, decaying across the documented jitter window
+    /// around that cooldown, and settling at 0.0 once the tile is stale (the
+    /// scan that covers it could be re-run at any moment, so we can no
+    /// longer vouch for what's there). Out-of-range tiles, and tiles whose
+    /// covering band hasn't been scanned yet, return 0.0.
+    fn certainty(&self, x: i8, y: i8) -> f32 {
+        let Some((_, scanned_at)) = self.at(x, y) else {
+            return 0.0;
+        };
+        if scanned_at == 0 {
+            // `Radar::new` seeds every band's scan time with this sentinel;
+            // nothing has actually been scanned there yet.
+            return 0.0;
         }
-        if bigger > 4 {
-            return None;
+        let (scan_size, _) = self.scan_time[covering_index(x, y).unwrap()];
+
+        let elapsed = timer_ticks().saturating_sub(scanned_at) as f32;
+        let cooldown = cooldown_ticks(scan_size) as f32;
+        let jitter = jitter_fraction(scan_size);
+        let lo = cooldown * (1.0 - jitter);
+        let hi = cooldown * (1.0 + jitter);
+
+        if elapsed <= lo {
+            1.0
+        } else if elapsed >= hi {
+            0.0
+        } else {
+            (hi - elapsed) / (hi - lo)
         }
-        let (scan_size, scanned_at) = self.scan_time[bigger - 1];
-        Some((radar_read(scan_size, x, y, 0) as u8 as char, scanned_at))
     }
 
     /// Scans in an area
@@ -78,17 +178,20 @@ impl Radar {
     ///
     /// # Errors
     ///
-    /// This function will return an error if the radar isn't ready
-    /// or
-    /// the given size is not 3, 5, 7, or 9.
-    fn scan(&mut self, size: usize) -> Result<(), Error> {
+    /// Returns [`RadarError::NotReady`] if the radar isn't ready yet (carrying
+    /// the estimated ticks remaining on the cooldown), or
+    /// [`RadarError::InvalidSize`] if `size` is not 3, 5, 7, or 9.
+    fn scan(&mut self, size: usize) -> Result<()> {
         if !self.ready() {
-            return Err(Error);
+            return Err(RadarError::NotReady {
+                time_to_ready: self.time_to_next_scan(),
+            });
         }
         match size {
             3 => {
                 radar_scan(3);
                 let time = timer_ticks();
+                self.history.push(3, time);
                 self.scan_time[0] = (3, time);
                 self.recent_scan_time = time;
                 self.recent_scan_type = 3;
@@ -97,6 +200,7 @@ impl Radar {
             5 => {
                 radar_scan(5);
                 let time = timer_ticks();
+                self.history.push(5, time);
                 self.scan_time[0] = (5, time);
                 self.scan_time[1] = (5, time);
                 self.recent_scan_type = 5;
@@ -106,6 +210,7 @@ impl Radar {
             7 => {
                 radar_scan(7);
                 let time = timer_ticks();
+                self.history.push(7, time);
                 self.scan_time[0] = (7, time);
                 self.scan_time[1] = (7, time);
                 self.scan_time[2] = (7, time);
@@ -116,27 +221,24 @@ impl Radar {
             9 => {
                 radar_scan(9);
                 let time = timer_ticks();
+                self.history.push(9, time);
                 self.scan_time = [(9, time); 4];
                 self.recent_scan_type = 9;
                 self.recent_scan_time = time;
                 Ok(())
             }
-            _ => Err(Error),
+            _ => Err(RadarError::InvalidSize { got: size }),
         }
     }
 
     /// Returns the time to next possible scan of this [`Radar`].
     /// There is a certian amount of error within this (check the documentation for [`at`][`Radar::at`].)
     fn time_to_next_scan(&self) -> u32 {
-        let time = timer_ticks();
-        let v = match self.recent_scan_type {
-            3 => 10_000,
-            5 => 15_000,
-            7 => 22_000,
-            9 => 30_000,
-            _ => 0,
-        };
-        v - time - self.recent_scan_time
+        ticks_until_ready(
+            timer_ticks(),
+            self.recent_scan_time,
+            cooldown_ticks(self.recent_scan_type),
+        )
     }
 
     /// Is the radar ready?
@@ -159,7 +261,17 @@ mod tests {
     use super::*;
 
     #[test]
-    fn time_to_next_scan() {
-        todo!();
+    fn time_to_next_scan_just_scanned() {
+        assert_eq!(ticks_until_ready(1_000, 1_000, 10_000), 10_000);
+    }
+
+    #[test]
+    fn time_to_next_scan_mid_cooldown() {
+        assert_eq!(ticks_until_ready(5_000, 1_000, 10_000), 6_000);
+    }
+
+    #[test]
+    fn time_to_next_scan_past_cooldown() {
+        assert_eq!(ticks_until_ready(50_000, 1_000, 10_000), 0);
     }
 }
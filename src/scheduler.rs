@@ -0,0 +1,211 @@
+use kartoffel::timer_ticks;
+
+use crate::{covering_index, Radar};
+
+/// How many tiles of interest a single [`ScanScheduler`] can track at once.
+/// Kept small since this runs on a memory-constrained bot.
+const TARGET_CAPACITY: usize = 8;
+
+/// Nominal scan sizes in the same band order as [`covering_index`].
+const SIZES: [usize; 4] = [3, 5, 7, 9];
+
+/// The smallest scan size that would cover the most-stale pending target in
+/// `targets`, or `None` if every target is fresh enough (or there aren't
+/// any) and a scan would be wasted.
+///
+/// Pulled out of [`ScanScheduler::next_scan`] as a pure function of
+/// `scan_time` (rather than reading through [`Radar::at`][`crate::Radar::at`],
+/// which would cost a hardware `radar_read` we don't need here) so it can be
+/// unit tested without a live radar.
+fn pick_scan_size(
+    scan_time: &[(usize, u32); 4],
+    targets: &[Option<ScanTarget>],
+    now: u32,
+) -> Option<usize> {
+    let most_stale = targets
+        .iter()
+        .flatten()
+        .filter_map(|target| {
+            let index = covering_index(target.x, target.y)?;
+            let (_, scanned_at) = scan_time[index];
+            let elapsed = now.saturating_sub(scanned_at);
+            (elapsed > target.max_staleness).then_some((elapsed, *target))
+        })
+        .max_by_key(|(elapsed, _)| *elapsed)?
+        .1;
+    let index = covering_index(most_stale.x, most_stale.y)?;
+    Some(SIZES[index])
+}
+
+/// A tile the caller wants the scheduler to keep fresh.
+#[derive(Clone, Copy)]
+pub struct ScanTarget {
+    pub x: i8,
+    pub y: i8,
+    /// How many ticks old this tile's data is allowed to get before the
+    /// scheduler considers it stale and worth re-scanning.
+    pub max_staleness: u32,
+}
+
+/// Servicing-the-nearest-deadline radar scheduler: register the tiles you
+/// care about keeping fresh, and let it decide when and how big to scan.
+///
+/// Like the Tock alarm capsule services whichever client's deadline is
+/// soonest, `ScanScheduler` always goes after the most-stale pending tile,
+/// picking the smallest (cheapest) scan size that covers it.
+pub struct ScanScheduler {
+    targets: [Option<ScanTarget>; TARGET_CAPACITY],
+}
+
+impl ScanScheduler {
+    pub fn new() -> Self {
+        ScanScheduler {
+            targets: [None; TARGET_CAPACITY],
+        }
+    }
+
+    /// Registers a tile to keep fresh. Returns `false` if the scheduler is
+    /// already tracking its full capacity of targets.
+    pub fn register(&mut self, target: ScanTarget) -> bool {
+        for slot in self.targets.iter_mut() {
+            if slot.is_none() {
+                *slot = Some(target);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// The smallest scan size that would cover the most-stale pending
+    /// target, or `None` if every registered target is fresh enough (or
+    /// nothing has been registered) and a scan would be wasted.
+    pub fn next_scan(&self, radar: &Radar) -> Option<usize> {
+        pick_scan_size(&radar.scan_time, &self.targets, timer_ticks())
+    }
+
+    /// Scans with whatever size [`next_scan`][Self::next_scan] recommends,
+    /// provided the radar is ready. Lets a bot delegate all radar cadence
+    /// decisions: cheap 3x3 scans keep near tiles fresh, and expensive 9x9
+    /// scans only fire once something far away has gone stale.
+    pub fn tick(&mut self, radar: &mut Radar) {
+        if !radar.ready() {
+            return;
+        }
+        if let Some(size) = self.next_scan(radar) {
+            let _ = radar.scan(size);
+        }
+    }
+}
+
+impl Default for ScanScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_time(ages: [u32; 4]) -> [(usize, u32); 4] {
+        [
+            (3, ages[0]),
+            (5, ages[1]),
+            (7, ages[2]),
+            (9, ages[3]),
+        ]
+    }
+
+    #[test]
+    fn prefers_cheap_scan_for_near_stale_target() {
+        // Every band was last refreshed at tick 0, but the only stale
+        // target is one tile away: a 3x3 re-scan covers it.
+        let scan_time = scan_time([0, 0, 0, 0]);
+        let targets = [
+            Some(ScanTarget {
+                x: 1,
+                y: 1,
+                max_staleness: 100,
+            }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ];
+        assert_eq!(pick_scan_size(&scan_time, &targets, 1_000), Some(3));
+    }
+
+    #[test]
+    fn reserves_expensive_scan_for_far_stale_target() {
+        let scan_time = scan_time([0, 0, 0, 0]);
+        let targets = [
+            Some(ScanTarget {
+                x: 4,
+                y: 0,
+                max_staleness: 100,
+            }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ];
+        assert_eq!(pick_scan_size(&scan_time, &targets, 1_000), Some(9));
+    }
+
+    #[test]
+    fn picks_the_most_stale_target_among_several() {
+        let scan_time = scan_time([0, 500, 0, 0]);
+        let targets = [
+            Some(ScanTarget {
+                x: 1,
+                y: 0,
+                max_staleness: 100,
+            }), // band 0, elapsed 1000
+            Some(ScanTarget {
+                x: 2,
+                y: 0,
+                max_staleness: 100,
+            }), // band 1, elapsed 500
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ];
+        assert_eq!(pick_scan_size(&scan_time, &targets, 1_000), Some(3));
+    }
+
+    #[test]
+    fn none_when_everything_is_fresh_enough() {
+        let scan_time = scan_time([900, 900, 900, 900]);
+        let targets = [
+            Some(ScanTarget {
+                x: 1,
+                y: 1,
+                max_staleness: 200,
+            }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        ];
+        assert_eq!(pick_scan_size(&scan_time, &targets, 1_000), None);
+    }
+
+    #[test]
+    fn none_when_no_targets_registered() {
+        let scan_time = scan_time([0, 0, 0, 0]);
+        let targets = [None; 8];
+        assert_eq!(pick_scan_size(&scan_time, &targets, 1_000), None);
+    }
+}
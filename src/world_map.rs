@@ -0,0 +1,227 @@
+use crate::Radar;
+
+/// Default side length of a [`WorldMap`]'s absolute grid, centered on
+/// `(0, 0)`. Each cell is a `char` (4 bytes) plus a `u32` tick (4 bytes), so
+/// the default map costs `32 * 32 * 8 = 8,192` bytes (8 KiB) of static
+/// state. Bots with a tighter memory budget, or a smaller known arena,
+/// should instantiate `WorldMap::<N>::new(..)` with a smaller `N` (e.g.
+/// `WorldMap::<16>::new(..)` for 2 KiB); bots that need to cover more
+/// ground can size it up the same way.
+pub const DEFAULT_WORLD_SIZE: usize = 32;
+
+/// The bot's current compass heading, used to rotate a bot-centric scan into
+/// absolute map coordinates.
+///
+/// Bot-centric offsets are rotated as if `North` means "no rotation", with
+/// each step clockwise rotating the offset a further 90 degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heading {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Heading {
+    fn rotate(self, dx: i8, dy: i8) -> (i8, i8) {
+        match self {
+            Heading::North => (dx, dy),
+            Heading::East => (-dy, dx),
+            Heading::South => (-dx, -dy),
+            Heading::West => (dy, -dx),
+        }
+    }
+}
+
+/// How a [`WorldMap`] folds a new observation of a tile into what it already
+/// remembers about that tile.
+pub enum Consolidation {
+    /// Always keep the freshest reading, discarding older ones.
+    KeepMostRecent,
+    /// Keep whichever reading ranks higher under the given priority
+    /// function, falling back to freshness on a tie.
+    ///
+    /// Useful for "once seen, always remembered" markers — e.g. rank walls
+    /// and enemies above empty space so a stale wall sighting isn't
+    /// overwritten just because the tile looked empty on a more recent, but
+    /// lower-fidelity, scan.
+    Max(fn(char) -> u8),
+}
+
+#[derive(Clone, Copy)]
+struct Cell {
+    value: char,
+    tick: u32,
+}
+
+impl Cell {
+    const fn unknown() -> Self {
+        Cell {
+            value: ' ',
+            tick: 0,
+        }
+    }
+
+    fn is_known(self) -> bool {
+        self.tick != 0
+    }
+}
+
+/// Opt-in subsystem that consolidates successive bot-centric [`Radar`] scans
+/// into a persistent absolute-coordinate map, so a bot can remember the
+/// arena across movement instead of re-scanning everything each step.
+///
+/// `SIZE` is the side length of the absolute grid (see [`DEFAULT_WORLD_SIZE`]
+/// for the memory/coverage tradeoff); most bots can just use `WorldMap`
+/// (i.e. `WorldMap<DEFAULT_WORLD_SIZE>`) and only need to name the const
+/// generic when they want a smaller or larger map.
+///
+/// The caller is responsible for tracking their own absolute position and
+/// heading (e.g. via motor/compass calls) and passing them to
+/// [`scan`][`WorldMap::scan`].
+pub struct WorldMap<const SIZE: usize = DEFAULT_WORLD_SIZE> {
+    cells: [[Cell; SIZE]; SIZE],
+    strategy: Consolidation,
+}
+
+impl<const SIZE: usize> WorldMap<SIZE> {
+    pub fn new(strategy: Consolidation) -> Self {
+        WorldMap {
+            cells: [[Cell::unknown(); SIZE]; SIZE],
+            strategy,
+        }
+    }
+
+    /// Maps an absolute coordinate to a grid index, or `None` if it falls
+    /// outside the area this map covers.
+    fn grid_index(abs: i32) -> Option<usize> {
+        let half = (SIZE / 2) as i32;
+        let shifted = abs + half;
+        if shifted < 0 || shifted >= SIZE as i32 {
+            None
+        } else {
+            Some(shifted as usize)
+        }
+    }
+
+    /// Folds a single observation into the cell at `(abs_x, abs_y)` using
+    /// this map's [`Consolidation`] strategy.
+    fn observe(&mut self, abs_x: i32, abs_y: i32, value: char, tick: u32) {
+        let (Some(gx), Some(gy)) = (Self::grid_index(abs_x), Self::grid_index(abs_y)) else {
+            return;
+        };
+        let cell = &mut self.cells[gy][gx];
+        let keep_new = match self.strategy {
+            Consolidation::KeepMostRecent => tick >= cell.tick,
+            Consolidation::Max(priority) => {
+                let new_rank = priority(value);
+                let old_rank = priority(cell.value);
+                new_rank > old_rank || (new_rank == old_rank && tick >= cell.tick)
+            }
+        };
+        if keep_new {
+            *cell = Cell { value, tick };
+        }
+    }
+
+    /// Consolidates everything `radar` currently knows, rotated/translated
+    /// from bot-centric into absolute coordinates given the bot's current
+    /// `(bot_x, bot_y)` position and `heading`.
+    pub fn scan(&mut self, radar: &Radar, bot_x: i32, bot_y: i32, heading: Heading) {
+        for dy in -4i8..=4 {
+            for dx in -4i8..=4 {
+                let Some((value, tick)) = radar.at(dx, dy) else {
+                    continue;
+                };
+                let (rx, ry) = heading.rotate(dx, dy);
+                self.observe(bot_x + rx as i32, bot_y + ry as i32, value, tick);
+            }
+        }
+    }
+
+    /// The consolidated reading for absolute tile `(abs_x, abs_y)`, plus the
+    /// tick it was last confirmed, or `None` if that tile has never been
+    /// observed (or falls outside the area this map covers).
+    pub fn at(&self, abs_x: i32, abs_y: i32) -> Option<(char, u32)> {
+        let gx = Self::grid_index(abs_x)?;
+        let gy = Self::grid_index(abs_y)?;
+        let cell = self.cells[gy][gx];
+        cell.is_known().then_some((cell.value, cell.tick))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate_north_is_identity() {
+        assert_eq!(Heading::North.rotate(1, 2), (1, 2));
+    }
+
+    #[test]
+    fn rotate_east_is_quarter_turn_clockwise() {
+        assert_eq!(Heading::East.rotate(1, 0), (0, 1));
+        assert_eq!(Heading::East.rotate(0, 1), (-1, 0));
+    }
+
+    #[test]
+    fn rotate_south_is_half_turn() {
+        assert_eq!(Heading::South.rotate(1, 2), (-1, -2));
+    }
+
+    #[test]
+    fn rotate_west_is_quarter_turn_counterclockwise() {
+        assert_eq!(Heading::West.rotate(1, 0), (0, -1));
+        assert_eq!(Heading::West.rotate(0, 1), (1, 0));
+    }
+
+    #[test]
+    fn unobserved_tile_is_none() {
+        let map = WorldMap::<8>::new(Consolidation::KeepMostRecent);
+        assert_eq!(map.at(0, 0), None);
+    }
+
+    #[test]
+    fn out_of_range_tile_is_none() {
+        let map = WorldMap::<8>::new(Consolidation::KeepMostRecent);
+        assert_eq!(map.at(100, 100), None);
+    }
+
+    #[test]
+    fn keep_most_recent_prefers_the_newer_tick() {
+        let mut map = WorldMap::<8>::new(Consolidation::KeepMostRecent);
+        map.observe(0, 0, '.', 100);
+        map.observe(0, 0, '#', 50); // older, should be ignored
+        assert_eq!(map.at(0, 0), Some(('.', 100)));
+
+        map.observe(0, 0, '@', 150); // newer, should replace
+        assert_eq!(map.at(0, 0), Some(('@', 150)));
+    }
+
+    #[test]
+    fn max_consolidation_keeps_the_higher_priority_reading_even_if_older() {
+        fn wall_priority(c: char) -> u8 {
+            if c == '#' {
+                1
+            } else {
+                0
+            }
+        }
+        let mut map = WorldMap::<8>::new(Consolidation::Max(wall_priority));
+        map.observe(0, 0, '#', 50);
+        map.observe(0, 0, '.', 200); // fresher, but lower priority
+        assert_eq!(map.at(0, 0), Some(('#', 50)));
+    }
+
+    #[test]
+    fn max_consolidation_breaks_ties_by_freshness() {
+        fn always_equal(_: char) -> u8 {
+            0
+        }
+        let mut map = WorldMap::<8>::new(Consolidation::Max(always_equal));
+        map.observe(0, 0, '.', 50);
+        map.observe(0, 0, '@', 100);
+        assert_eq!(map.at(0, 0), Some(('@', 100)));
+    }
+}